@@ -2,12 +2,46 @@
 use std::cmp::max;
 use std::fmt::{Display, Formatter};
 use std::mem;
+use std::ops::{Bound, RangeBounds};
 
-pub struct Leaderboard {
-    root: Option<AVLNode>
+/// An associative aggregate that can be cached per-subtree alongside the
+/// existing `height`/`children` counters, so range queries over a custom
+/// summary (sum, count, max, ...) run in O(log n) instead of walking every
+/// node. `combine` must be associative so that summaries can be merged in
+/// any order the tree happens to be shaped in.
+pub trait ScoreMonoid {
+    type Summary: Clone;
+
+    /// The identity element: `combine(unit(), x) == x` for all `x`.
+    fn unit() -> Self::Summary;
+
+    /// Lift a single node's score (and how many players share it) into a summary.
+    fn lift(score: u64, n_players: usize) -> Self::Summary;
+
+    /// Combine two summaries, in left-to-right order.
+    fn combine(a: Self::Summary, b: Self::Summary) -> Self::Summary;
+}
+
+/// The default monoid used when a caller doesn't need a range aggregate.
+/// Its `Summary` is zero-sized, so it costs nothing beyond what the tree
+/// already tracks.
+pub struct NoopMonoid;
+
+impl ScoreMonoid for NoopMonoid {
+    type Summary = ();
+
+    fn unit() -> Self::Summary {}
+
+    fn lift(_score: u64, _n_players: usize) -> Self::Summary {}
+
+    fn combine(_a: Self::Summary, _b: Self::Summary) -> Self::Summary {}
+}
+
+pub struct Leaderboard<M: ScoreMonoid = NoopMonoid> {
+    root: Option<AVLNode<M>>
 }
 
-impl Leaderboard {
+impl<M: ScoreMonoid> Leaderboard<M> {
     /// Create a new AVL tree
     pub fn new() -> Self {
         Leaderboard { root: None }
@@ -18,14 +52,7 @@ impl Leaderboard {
         if let Some(ref mut inner) = self.root {
             inner.insert(player_id, score);
         } else {
-            self.root = Some(AVLNode {
-                player_id: vec![player_id.as_ref().to_owned()],
-                score,
-                right: None,
-                left: None,
-                height: 1,
-                children: 0
-            });
+            self.root = Some(AVLNode::new(player_id, score));
         }
     }
 
@@ -63,8 +90,96 @@ impl Leaderboard {
             None
         }
     }
-    
-    pub fn pre_order(&self) -> LeaderboardIter {
+
+    /// Look up the entry occupying a given 1-based rank (1 = highest score).
+    /// This is the inverse of `rank_of` and, like it, runs in O(log n) by
+    /// descending the tree using the `children` subtree-size counters.
+    pub fn select_by_rank(&self, rank: usize) -> Option<(&Vec<String>, u64)> {
+        if rank < 1 {
+            return None;
+        }
+
+        if let Some(ref inner) = self.root {
+            inner.select_by_rank(rank)
+        } else {
+            None
+        }
+    }
+
+    /// Count players with a score in `[lo, hi]` in O(log n) using the cached
+    /// per-subtree `player_count` totals, instead of scanning every node.
+    pub fn count_in_range(&self, lo: u64, hi: u64) -> usize {
+        if lo > hi {
+            return 0;
+        }
+
+        match &self.root {
+            Some(inner) => inner.count_less_equal(hi) - inner.count_less_than(lo),
+            None => 0
+        }
+    }
+
+    /// List all players with a score in `[lo, hi]`.
+    pub fn players_in_range(&self, lo: u64, hi: u64) -> Vec<(String, u64)> {
+        if lo > hi {
+            return Vec::new();
+        }
+
+        match &self.root {
+            Some(inner) => inner.players_in_range(lo, hi),
+            None => Vec::new()
+        }
+    }
+
+    /// Aggregate the custom `M::Summary` over every entry with a score in
+    /// `[lo, hi]`, in O(log n), by reusing whichever cached subtree
+    /// summaries are already known to fall entirely inside the range.
+    pub fn range_summary(&self, lo: u64, hi: u64) -> M::Summary {
+        if lo > hi {
+            return M::unit();
+        }
+
+        match &self.root {
+            Some(inner) => inner.range_summary(lo, hi),
+            None => M::unit()
+        }
+    }
+
+    /// Split off all entries with a score `< pivot` into one leaderboard and
+    /// `>= pivot` into another, preserving the AVL balance invariant in both.
+    pub fn split_at_score(self, pivot: u64) -> (Leaderboard<M>, Leaderboard<M>) {
+        let (low, high) = split(self.root.map(Box::new), pivot);
+        (Leaderboard { root: low.map(|b| *b) }, Leaderboard { root: high.map(|b| *b) })
+    }
+
+    /// Merge `other` into this leaderboard. The two leaderboards' score
+    /// ranges must be disjoint (e.g. the two halves of a prior
+    /// `split_at_score`, or two regional leaderboards with non-overlapping
+    /// score bands); the balance invariant is preserved.
+    ///
+    /// Panics if the score ranges overlap.
+    pub fn merge(&mut self, mut other: Leaderboard<M>) {
+        let ours = self.root.take().map(Box::new);
+        let theirs = other.root.take().map(Box::new);
+
+        let joined = match (&ours, &theirs) {
+            (None, _) => theirs,
+            (_, None) => ours,
+            (Some(o), Some(t)) => {
+                if subtree_max_score(o) < subtree_min_score(t) {
+                    join(ours, theirs)
+                } else if subtree_max_score(t) < subtree_min_score(o) {
+                    join(theirs, ours)
+                } else {
+                    panic!("Leaderboard::merge requires disjoint score ranges")
+                }
+            }
+        };
+
+        self.root = joined.map(|b| *b);
+    }
+
+    pub fn pre_order(&self) -> LeaderboardIter<M> {
         LeaderboardIter {
             nodes: match &self.root {
                 Some(r) => vec![r],
@@ -72,9 +187,28 @@ impl Leaderboard {
             }
         }
     }
+
+    /// Entries in ascending score order, lowest first. Lazy, stack-based
+    /// in-order traversal -- no intermediate `Vec` is built.
+    pub fn iter_asc(&self) -> AscIter<M> {
+        AscIter::new(self.root.as_ref())
+    }
+
+    /// Entries in descending score order, highest first -- the natural
+    /// leaderboard view. Lazy, stack-based in-order traversal.
+    pub fn iter_desc(&self) -> DescIter<M> {
+        DescIter::new(self.root.as_ref())
+    }
+
+    /// Entries with a score in `bounds`, in ascending order. Subtrees entirely
+    /// outside the bounds are pruned rather than visited, so this stays cheap
+    /// even when the range only covers a small slice of the tree.
+    pub fn range(&self, bounds: impl RangeBounds<u64>) -> RangeIter<M> {
+        RangeIter::new(self.root.as_ref(), clone_bound(bounds.start_bound()), clone_bound(bounds.end_bound()))
+    }
 }
 
-impl Display for Leaderboard {
+impl<M: ScoreMonoid> Display for Leaderboard<M> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match &self.root {
             Some(r) => r.to_string(),
@@ -84,13 +218,13 @@ impl Display for Leaderboard {
 }
 
 /// An iterator over the leaderboard entries. This does pre-order traversal.
-pub struct LeaderboardIter<'a> {
-    nodes: Vec<&'a AVLNode>
+pub struct LeaderboardIter<'a, M: ScoreMonoid = NoopMonoid> {
+    nodes: Vec<&'a AVLNode<M>>
 }
 
-impl<'a> Iterator for LeaderboardIter<'a> {
+impl<'a, M: ScoreMonoid> Iterator for LeaderboardIter<'a, M> {
     type Item = (&'a Vec<String>, u64);
-    
+
     fn next(&mut self) -> Option<Self::Item> {
         if let Some(n) = self.nodes.pop() {
             if let Some(ref right) = n.right {
@@ -107,20 +241,162 @@ impl<'a> Iterator for LeaderboardIter<'a> {
     }
 }
 
+/// A lazy, stack-based in-order iterator yielding entries from lowest to
+/// highest score. See `Leaderboard::iter_asc`.
+pub struct AscIter<'a, M: ScoreMonoid> {
+    stack: Vec<&'a AVLNode<M>>
+}
+
+impl<'a, M: ScoreMonoid> AscIter<'a, M> {
+    fn new(root: Option<&'a AVLNode<M>>) -> Self {
+        let mut stack = Vec::new();
+        Self::push_left_spine(root, &mut stack);
+        AscIter { stack }
+    }
+
+    fn push_left_spine(mut node: Option<&'a AVLNode<M>>, stack: &mut Vec<&'a AVLNode<M>>) {
+        while let Some(n) = node {
+            stack.push(n);
+            node = n.left.as_deref();
+        }
+    }
+}
+
+impl<'a, M: ScoreMonoid> Iterator for AscIter<'a, M> {
+    type Item = (&'a Vec<String>, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        Self::push_left_spine(node.right.as_deref(), &mut self.stack);
+        Some((&node.player_id, node.score))
+    }
+}
+
+/// A lazy, stack-based in-order iterator yielding entries from highest to
+/// lowest score. See `Leaderboard::iter_desc`.
+pub struct DescIter<'a, M: ScoreMonoid> {
+    stack: Vec<&'a AVLNode<M>>
+}
+
+impl<'a, M: ScoreMonoid> DescIter<'a, M> {
+    fn new(root: Option<&'a AVLNode<M>>) -> Self {
+        let mut stack = Vec::new();
+        Self::push_right_spine(root, &mut stack);
+        DescIter { stack }
+    }
+
+    fn push_right_spine(mut node: Option<&'a AVLNode<M>>, stack: &mut Vec<&'a AVLNode<M>>) {
+        while let Some(n) = node {
+            stack.push(n);
+            node = n.right.as_deref();
+        }
+    }
+}
+
+impl<'a, M: ScoreMonoid> Iterator for DescIter<'a, M> {
+    type Item = (&'a Vec<String>, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        Self::push_right_spine(node.left.as_deref(), &mut self.stack);
+        Some((&node.player_id, node.score))
+    }
+}
+
+#[inline]
+fn clone_bound(bound: Bound<&u64>) -> Bound<u64> {
+    match bound {
+        Bound::Included(b) => Bound::Included(*b),
+        Bound::Excluded(b) => Bound::Excluded(*b),
+        Bound::Unbounded => Bound::Unbounded
+    }
+}
+
+#[inline]
+fn satisfies_lower_bound(score: u64, bound: &Bound<u64>) -> bool {
+    match bound {
+        Bound::Unbounded => true,
+        Bound::Included(b) => score >= *b,
+        Bound::Excluded(b) => score > *b
+    }
+}
+
+#[inline]
+fn satisfies_upper_bound(score: u64, bound: &Bound<u64>) -> bool {
+    match bound {
+        Bound::Unbounded => true,
+        Bound::Included(b) => score <= *b,
+        Bound::Excluded(b) => score < *b
+    }
+}
+
+/// A lazy, stack-based in-order iterator over entries whose score falls
+/// within a given range. See `Leaderboard::range`.
+pub struct RangeIter<'a, M: ScoreMonoid> {
+    stack: Vec<&'a AVLNode<M>>,
+    lo: Bound<u64>,
+    hi: Bound<u64>
+}
+
+impl<'a, M: ScoreMonoid> RangeIter<'a, M> {
+    fn new(root: Option<&'a AVLNode<M>>, lo: Bound<u64>, hi: Bound<u64>) -> Self {
+        let mut stack = Vec::new();
+        Self::push_left_spine(root, &lo, &mut stack);
+        RangeIter { stack, lo, hi }
+    }
+
+    // descend the left spine, but whenever a node's score can't satisfy the
+    // lower bound, its left subtree can't either (BST order), so skip straight
+    // to its right child instead of pushing it
+    fn push_left_spine(mut node: Option<&'a AVLNode<M>>, lo: &Bound<u64>, stack: &mut Vec<&'a AVLNode<M>>) {
+        while let Some(n) = node {
+            if satisfies_lower_bound(n.score, lo) {
+                stack.push(n);
+                node = n.left.as_deref();
+            } else {
+                node = n.right.as_deref();
+            }
+        }
+    }
+}
+
+impl<'a, M: ScoreMonoid> Iterator for RangeIter<'a, M> {
+    type Item = (&'a Vec<String>, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+
+        if !satisfies_upper_bound(node.score, &self.hi) {
+            // everything else on the stack is even larger (ascending order), so
+            // nothing further can satisfy the upper bound either
+            self.stack.clear();
+            return None;
+        }
+
+        Self::push_left_spine(node.right.as_deref(), &self.lo, &mut self.stack);
+        Some((&node.player_id, node.score))
+    }
+}
+
 /* Private API */
 
-struct AVLNode {
+struct AVLNode<M: ScoreMonoid> {
     // It is possible for multiple players to achieve the exact same score.
     // For simplicity, we will store these in the same node.
     player_id: Vec<String>,
     score: u64,
-    right: Option<Box<AVLNode>>,
-    left: Option<Box<AVLNode>>,
+    right: Option<Box<AVLNode<M>>>,
+    left: Option<Box<AVLNode<M>>>,
     height: isize,
-    children: usize
+    children: usize,
+    // total number of players in this subtree, i.e. the sum of `player_id.len()`
+    // across every node here -- unlike `children`, this counts players, not nodes,
+    // so ties (several players sharing a score) aren't undercounted
+    player_count: usize,
+    summary: M::Summary
 }
 
-impl AVLNode {
+impl<M: ScoreMonoid> AVLNode<M> {
     fn new(player: impl AsRef<str>, score: u64) -> Self {
         Self {
             player_id: vec![player.as_ref().to_owned()],
@@ -129,6 +405,8 @@ impl AVLNode {
             left: None,
             height: 1,
             children: 0,
+            player_count: 1,
+            summary: M::lift(score, 1)
         }
     }
 
@@ -152,6 +430,17 @@ impl AVLNode {
         let left_children = self.left.as_ref().map(|l| l.children + 1).unwrap_or(0);
         let right_children = self.right.as_ref().map(|l| l.children + 1).unwrap_or(0);
         self.children = left_children + right_children;
+
+        // total players in this subtree (for player-counting range queries)
+        let left_players = self.left.as_ref().map(|l| l.player_count).unwrap_or(0);
+        let right_players = self.right.as_ref().map(|r| r.player_count).unwrap_or(0);
+        self.player_count = left_players + self.player_id.len() + right_players;
+
+        // aggregate summary, left-to-right across left subtree, this node, right subtree
+        let left_summary = self.left.as_ref().map(|l| l.summary.clone()).unwrap_or_else(M::unit);
+        let right_summary = self.right.as_ref().map(|r| r.summary.clone()).unwrap_or_else(M::unit);
+        let own_summary = M::lift(self.score, self.player_id.len());
+        self.summary = M::combine(left_summary, M::combine(own_summary, right_summary));
     }
 
     #[inline]
@@ -163,7 +452,9 @@ impl AVLNode {
         // Insert the node, recursively.
         if self.score == score {
             self.player_id.push(player.as_ref().to_owned());
-            // nothing else changes.
+            // height/children are unaffected, but the summary depends on
+            // how many players share this score, so it still needs a refresh
+            self.update_attrs();
             return;
         } else if score < self.score {
             if let Some(ref mut left_node) = self.left {
@@ -191,7 +482,10 @@ impl AVLNode {
         // right heavy
         if self.height_right() - self.height_left() > 1 {
             let rr_heavy = match self.right {
-                Some(ref r) => r.height_right() > r.height_left(),
+                // >= (not >): a balanced child (height_right == height_left, which
+                // split/join/deletion can produce) still needs the single rotation,
+                // not the double one
+                Some(ref r) => r.height_right() >= r.height_left(),
                 None => unreachable!()
             };
 
@@ -203,7 +497,7 @@ impl AVLNode {
             }
         } else { // left heavy
             let ll_heavy = match self.left {
-                Some(ref r) => r.height_left() > r.height_right(),
+                Some(ref r) => r.height_left() >= r.height_right(),
                 None => unreachable!()
             };
 
@@ -384,7 +678,7 @@ impl AVLNode {
         results.extend(self.player_id.iter().enumerate()
             .take_while(|(i, v)| *i < rem)
             .map(|(_, v)| (v.clone(), self.score)));
-        
+
         // if we still need more elements, try the left child
         rem = n - results.len();
         if let Some(ref left) = self.left {
@@ -394,20 +688,34 @@ impl AVLNode {
         results
     }
 
+    fn select_by_rank(&self, rank: usize) -> Option<(&Vec<String>, u64)> {
+        // the right subtree always holds the higher scores (and thus the
+        // better ranks), so its size tells us where this node's rank falls
+        let right_tree_size = self.right.as_ref().map(|r| r.children + 1).unwrap_or(0);
+
+        if rank <= right_tree_size {
+            self.right.as_ref().unwrap().select_by_rank(rank)
+        } else if rank == right_tree_size + 1 {
+            Some((&self.player_id, self.score))
+        } else {
+            self.left.as_ref().and_then(|l| l.select_by_rank(rank - right_tree_size - 1))
+        }
+    }
+
     pub fn rank_of(&self, player: impl AsRef<str>, score: u64, num_better: usize) -> Option<usize> {
         // nodes can potentially store more than one player inside, but because
         // we assign the same rank number to ties, we can ignore this fact in
         // this method.
-        
+
         let ply_id = player.as_ref();
         let right_tree_size = self.right.as_ref().map(|r| r.children + 1 /* include this node too */).unwrap_or(0);
-        
+
         if self.score == score {
             if self.player_id.iter().filter(|v| **v == ply_id).count() < 1  {
                 // ???
                 return None;
             }
-            
+
             Some(1 + right_tree_size + num_better)
         } else if score < self.score {
             if let Some(ref left) = self.left {
@@ -423,7 +731,96 @@ impl AVLNode {
             }
         }
     }
-    
+
+    // number of players with a score strictly less than `x`; O(log n) via the
+    // `player_count` totals, only descending into whichever side could still hold entries
+    fn count_less_than(&self, x: u64) -> usize {
+        if self.score < x {
+            let left_size = self.left.as_ref().map(|l| l.player_count).unwrap_or(0);
+            left_size + self.player_id.len()
+                + self.right.as_ref().map(|r| r.count_less_than(x)).unwrap_or(0)
+        } else {
+            self.left.as_ref().map(|l| l.count_less_than(x)).unwrap_or(0)
+        }
+    }
+
+    // number of players with a score less than or equal to `x`; same idea as count_less_than
+    fn count_less_equal(&self, x: u64) -> usize {
+        if self.score <= x {
+            let left_size = self.left.as_ref().map(|l| l.player_count).unwrap_or(0);
+            left_size + self.player_id.len()
+                + self.right.as_ref().map(|r| r.count_less_equal(x)).unwrap_or(0)
+        } else {
+            self.left.as_ref().map(|l| l.count_less_equal(x)).unwrap_or(0)
+        }
+    }
+
+    fn players_in_range(&self, lo: u64, hi: u64) -> Vec<(String, u64)> {
+        let mut results = Vec::new();
+
+        if self.score < lo {
+            if let Some(ref right) = self.right {
+                results.extend(right.players_in_range(lo, hi));
+            }
+            return results;
+        }
+
+        if self.score > hi {
+            if let Some(ref left) = self.left {
+                results.extend(left.players_in_range(lo, hi));
+            }
+            return results;
+        }
+
+        if let Some(ref left) = self.left {
+            results.extend(left.players_in_range(lo, hi));
+        }
+        results.extend(self.player_id.iter().map(|p| (p.clone(), self.score)));
+        if let Some(ref right) = self.right {
+            results.extend(right.players_in_range(lo, hi));
+        }
+
+        results
+    }
+
+    // aggregate M::Summary over every entry in [lo, hi]. `known_lo`/`known_hi`
+    // are exclusive bounds already established by the path taken to reach
+    // this node (None = unbounded); once they prove the whole subtree sits
+    // inside [lo, hi] we can reuse `self.summary` instead of descending.
+    fn range_summary_bounded(&self, lo: u64, hi: u64, known_lo: Option<u64>, known_hi: Option<u64>) -> M::Summary {
+        let lo_satisfied = known_lo.map(|b| b.saturating_add(1) >= lo).unwrap_or(false);
+        let hi_satisfied = known_hi.map(|b| b.saturating_sub(1) <= hi).unwrap_or(false);
+        if lo_satisfied && hi_satisfied {
+            return self.summary.clone();
+        }
+
+        if self.score < lo {
+            return self.right.as_ref()
+                .map(|r| r.range_summary_bounded(lo, hi, Some(self.score), known_hi))
+                .unwrap_or_else(M::unit);
+        }
+
+        if self.score > hi {
+            return self.left.as_ref()
+                .map(|l| l.range_summary_bounded(lo, hi, known_lo, Some(self.score)))
+                .unwrap_or_else(M::unit);
+        }
+
+        let left_summary = self.left.as_ref()
+            .map(|l| l.range_summary_bounded(lo, hi, known_lo, Some(self.score)))
+            .unwrap_or_else(M::unit);
+        let right_summary = self.right.as_ref()
+            .map(|r| r.range_summary_bounded(lo, hi, Some(self.score), known_hi))
+            .unwrap_or_else(M::unit);
+        let own_summary = M::lift(self.score, self.player_id.len());
+
+        M::combine(left_summary, M::combine(own_summary, right_summary))
+    }
+
+    fn range_summary(&self, lo: u64, hi: u64) -> M::Summary {
+        self.range_summary_bounded(lo, hi, None, None)
+    }
+
     fn format_string(&self, mut buf: &mut String, level: usize) {
         // player list at node
         let mut players = self.player_id.iter()
@@ -435,7 +832,7 @@ impl AVLNode {
                 acc.push_str(ply);
                 acc
             });
-        
+
         // score, height, children
         players.push('(');
         players.push_str(&self.score.to_string());
@@ -444,10 +841,10 @@ impl AVLNode {
         players.push_str(", ");
         players.push_str(&self.children.to_string());
         players.push(')');
-        
-        
+
+
         let padding = "\t".repeat(level);
-        
+
         buf.push_str(&format!("{padding}{players}\n{padding}right:\n"));
         match &self.right {
             Some(rn) => rn.format_string(&mut buf, level + 1),
@@ -463,10 +860,193 @@ impl AVLNode {
 
 }
 
-impl Display for AVLNode {
+fn subtree_min_score<M: ScoreMonoid>(node: &AVLNode<M>) -> u64 {
+    match &node.left {
+        Some(l) => subtree_min_score(l),
+        None => node.score
+    }
+}
+
+fn subtree_max_score<M: ScoreMonoid>(node: &AVLNode<M>) -> u64 {
+    match &node.right {
+        Some(r) => subtree_max_score(r),
+        None => node.score
+    }
+}
+
+// Split `node` into (everything with score < pivot, everything with score >= pivot),
+// preserving AVL balance by re-joining each side's leftover subtree through `join_with_mid`.
+fn split<M: ScoreMonoid>(node: Option<Box<AVLNode<M>>>, pivot: u64) -> (Option<Box<AVLNode<M>>>, Option<Box<AVLNode<M>>>) {
+    match node {
+        None => (None, None),
+        Some(mut n) => {
+            let left = n.left.take();
+            let right = n.right.take();
+
+            if n.score < pivot {
+                let (split_left, split_right) = split(right, pivot);
+                (join_with_mid(left, n, split_left), split_right)
+            } else {
+                let (split_left, split_right) = split(left, pivot);
+                (split_left, join_with_mid(split_right, n, right))
+            }
+        }
+    }
+}
+
+// Remove and return the minimum-scoring node from `node`, along with the remainder
+// of the tree (still AVL-balanced) with that node removed.
+fn pop_min<M: ScoreMonoid>(mut node: Box<AVLNode<M>>) -> (Box<AVLNode<M>>, Option<Box<AVLNode<M>>>) {
+    match node.left.take() {
+        None => {
+            let right = node.right.take();
+            (node, right)
+        }
+        Some(left) => {
+            let (min_node, new_left) = pop_min(left);
+            node.left = new_left;
+            node.update_attrs();
+            node.rebalance_if_needed();
+            (min_node, Some(node))
+        }
+    }
+}
+
+// Join a `left` tree (every score strictly less than `right`'s) and a `right` tree
+// into a single AVL-balanced tree, reusing one of `right`'s nodes as the connecting
+// root rather than re-inserting every element.
+fn join<M: ScoreMonoid>(left: Option<Box<AVLNode<M>>>, right: Option<Box<AVLNode<M>>>) -> Option<Box<AVLNode<M>>> {
+    match (left, right) {
+        (None, right) => right,
+        (left, None) => left,
+        (left, Some(right)) => {
+            let (mid, remainder) = pop_min(right);
+            join_with_mid(left, mid, remainder)
+        }
+    }
+}
+
+// Join `left`, a single standalone `mid` node (its own children are ignored), and
+// `right` into one AVL-balanced tree. Descends down whichever side is taller until
+// the heights are within 1 of each other, attaches `mid` there, and rebalances back
+// up the spine by calling `rebalance_if_needed` on each node along it.
+fn join_with_mid<M: ScoreMonoid>(left: Option<Box<AVLNode<M>>>, mut mid: Box<AVLNode<M>>, right: Option<Box<AVLNode<M>>>) -> Option<Box<AVLNode<M>>> {
+    let left_height = left.as_ref().map(|l| l.height).unwrap_or(0);
+    let right_height = right.as_ref().map(|r| r.height).unwrap_or(0);
+
+    if left_height > right_height + 1 {
+        let mut l = left.unwrap();
+        l.right = join_with_mid(l.right.take(), mid, right);
+        l.update_attrs();
+        l.rebalance_if_needed();
+        Some(l)
+    } else if right_height > left_height + 1 {
+        let mut r = right.unwrap();
+        r.left = join_with_mid(left, mid, r.left.take());
+        r.update_attrs();
+        r.rebalance_if_needed();
+        Some(r)
+    } else {
+        mid.left = left;
+        mid.right = right;
+        mid.update_attrs();
+        Some(mid)
+    }
+}
+
+impl<M: ScoreMonoid> Display for AVLNode<M> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut buf = String::new();
         self.format_string(&mut buf, 0);
         write!(f, "{}", buf)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Small deterministic PRNG so the randomized tests below don't need an
+    // external `rand` dependency. Not cryptographic -- just needs to spread
+    // scores and choices around for coverage.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next_u64(&mut self, bound: u64) -> u64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            self.0 % bound
+        }
+    }
+
+    fn assert_avl_balanced<M: ScoreMonoid>(node: &AVLNode<M>) {
+        assert!(!node.unbalanced(), "node at score {} is unbalanced (heights {}/{})",
+            node.score, node.height_left(), node.height_right());
+
+        if let Some(ref l) = node.left {
+            assert_avl_balanced(l);
+        }
+        if let Some(ref r) = node.right {
+            assert_avl_balanced(r);
+        }
+    }
+
+    fn assert_leaderboard_balanced<M: ScoreMonoid>(board: &Leaderboard<M>) {
+        if let Some(ref root) = board.root {
+            assert_avl_balanced(root);
+        }
+    }
+
+    #[test]
+    fn insert_keeps_avl_balance() {
+        let mut rng = Lcg(42);
+        let mut board: Leaderboard = Leaderboard::new();
+
+        for i in 0..2000 {
+            board.insert(format!("p{i}"), rng.next_u64(500));
+            assert_leaderboard_balanced(&board);
+        }
+    }
+
+    #[test]
+    fn delete_keeps_avl_balance() {
+        let mut rng = Lcg(7);
+        let mut board: Leaderboard = Leaderboard::new();
+        let mut players = Vec::new();
+
+        for i in 0..2000 {
+            let player = format!("p{i}");
+            board.insert(&player, rng.next_u64(1000));
+            players.push(player);
+        }
+
+        for player in players {
+            if rng.next_u64(2) == 0 {
+                board.delete_player(player);
+                assert_leaderboard_balanced(&board);
+            }
+        }
+    }
+
+    #[test]
+    fn split_and_merge_keep_avl_balance() {
+        let mut rng = Lcg(1337);
+
+        for trial in 0..200 {
+            let mut board: Leaderboard = Leaderboard::new();
+            let n = 1 + rng.next_u64(40) as usize;
+
+            for i in 0..n {
+                board.insert(format!("p{trial}-{i}"), rng.next_u64(1000));
+            }
+
+            let pivot = rng.next_u64(1000);
+            let (low, high) = board.split_at_score(pivot);
+            assert_leaderboard_balanced(&low);
+            assert_leaderboard_balanced(&high);
+
+            let mut merged = low;
+            merged.merge(high);
+            assert_leaderboard_balanced(&merged);
+        }
+    }
+}
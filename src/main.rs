@@ -1,10 +1,11 @@
 use crate::avl::Leaderboard;
 
 mod avl;
+mod persistent;
 
 
 pub fn main() {
-    let mut leader = Leaderboard::new();
+    let mut leader: Leaderboard = Leaderboard::new();
 
     // from the prof's example
     leader.insert("A", 150);
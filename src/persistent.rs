@@ -0,0 +1,495 @@
+/* Public API */
+use std::cmp::max;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// A persistent (immutable) counterpart to `avl::Leaderboard`. `insert` and
+/// the `delete_*` methods take `&self` and return a *new* `PersistentLeaderboard`
+/// rather than mutating in place; unchanged subtrees are shared with the
+/// original via `Rc` instead of being copied. Only the O(log n) nodes on the
+/// path to the edited leaf are cloned, so keeping old snapshots around (for
+/// a "leaderboard as of yesterday" view, or undo) costs O(log n) extra memory
+/// per edit instead of O(n).
+pub struct PersistentLeaderboard {
+    root: Option<Rc<PersistentNode>>
+}
+
+impl PersistentLeaderboard {
+    /// Create a new, empty persistent leaderboard.
+    pub fn new() -> Self {
+        PersistentLeaderboard { root: None }
+    }
+
+    /// Return a new leaderboard with the score inserted, sharing every
+    /// subtree that wasn't on the path to the insertion point.
+    pub fn insert(&self, player_id: impl AsRef<str>, score: u64) -> Self {
+        let root = match &self.root {
+            Some(inner) => Some(inner.insert(player_id, score)),
+            None => Some(Rc::new(PersistentNode::new(player_id, score)))
+        };
+
+        PersistentLeaderboard { root }
+    }
+
+    /// Return a new leaderboard with all of a player's scores removed.
+    pub fn delete_player(&self, player_id: impl AsRef<str>) -> Self {
+        let root = self.root.as_ref().and_then(|inner| inner.delete_player(player_id));
+        PersistentLeaderboard { root }
+    }
+
+    /// Return a new leaderboard with a specific score of a player's removed.
+    pub fn delete_player_score(&self, player_id: impl AsRef<str>, score: u64) -> Self {
+        let root = self.root.as_ref().and_then(|inner| inner.delete_player_score(player_id, score));
+        PersistentLeaderboard { root }
+    }
+
+    pub fn top_n_players(&self, n: usize) -> Vec<(String, u64)> {
+        match &self.root {
+            Some(inner) => inner.top_n_players(n),
+            None => Vec::new()
+        }
+    }
+
+    pub fn rank_of(&self, player: impl AsRef<str>, score: u64) -> Option<usize> {
+        self.root.as_ref().and_then(|inner| inner.rank_of(player, score, 0))
+    }
+
+    pub fn pre_order(&self) -> PersistentIter {
+        PersistentIter {
+            nodes: match &self.root {
+                Some(r) => vec![Rc::clone(r)],
+                None => Vec::new()
+            }
+        }
+    }
+}
+
+impl Display for PersistentLeaderboard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match &self.root {
+            Some(r) => r.to_string(),
+            None => "None".to_owned()
+        })
+    }
+}
+
+/// An iterator over the leaderboard entries. This does pre-order traversal.
+pub struct PersistentIter {
+    nodes: Vec<Rc<PersistentNode>>
+}
+
+impl Iterator for PersistentIter {
+    type Item = (Vec<String>, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(n) = self.nodes.pop() {
+            if let Some(ref right) = n.right {
+                self.nodes.push(Rc::clone(right));
+            }
+            if let Some(ref left) = n.left {
+                self.nodes.push(Rc::clone(left));
+            }
+
+            Some((n.player_id.clone(), n.score))
+        } else {
+            None
+        }
+    }
+}
+
+/* Private API */
+
+struct PersistentNode {
+    // It is possible for multiple players to achieve the exact same score.
+    // For simplicity, we will store these in the same node.
+    player_id: Vec<String>,
+    score: u64,
+    right: Option<Rc<PersistentNode>>,
+    left: Option<Rc<PersistentNode>>,
+    height: isize,
+    children: usize
+}
+
+impl PersistentNode {
+    fn new(player: impl AsRef<str>, score: u64) -> Self {
+        Self {
+            player_id: vec![player.as_ref().to_owned()],
+            score,
+            right: None,
+            left: None,
+            height: 1,
+            children: 0
+        }
+    }
+
+    #[inline]
+    fn height_left(&self) -> isize {
+        self.left.as_ref().map(|l| l.height).unwrap_or(0)
+    }
+
+    #[inline]
+    fn height_right(&self) -> isize {
+        self.right.as_ref().map(|r| r.height).unwrap_or(0)
+    }
+
+    #[inline]
+    fn with_attrs(mut self) -> Self {
+        self.height = max(self.height_left(), self.height_right()) + 1;
+
+        let left_children = self.left.as_ref().map(|l| l.children + 1).unwrap_or(0);
+        let right_children = self.right.as_ref().map(|r| r.children + 1).unwrap_or(0);
+        self.children = left_children + right_children;
+
+        self
+    }
+
+    #[inline]
+    fn unbalanced(&self) -> bool {
+        isize::abs_diff(self.height_left(), self.height_right()) > 1
+    }
+
+    // Returns a clone-on-write copy of the path from this node down to the
+    // inserted leaf; every subtree untouched by the insert is `Rc::clone`d
+    // rather than copied.
+    fn insert(&self, player: impl AsRef<str>, score: u64) -> Rc<Self> {
+        let node = if self.score == score {
+            let mut player_id = self.player_id.clone();
+            player_id.push(player.as_ref().to_owned());
+
+            Self {
+                player_id,
+                score: self.score,
+                right: self.right.clone(),
+                left: self.left.clone(),
+                height: self.height,
+                children: self.children
+            }
+        } else if score < self.score {
+            let left = match &self.left {
+                Some(l) => l.insert(player, score),
+                None => Rc::new(Self::new(player, score))
+            };
+
+            Self {
+                player_id: self.player_id.clone(),
+                score: self.score,
+                right: self.right.clone(),
+                left: Some(left),
+                height: self.height,
+                children: self.children
+            }.with_attrs()
+        } else {
+            let right = match &self.right {
+                Some(r) => r.insert(player, score),
+                None => Rc::new(Self::new(player, score))
+            };
+
+            Self {
+                player_id: self.player_id.clone(),
+                score: self.score,
+                right: Some(right),
+                left: self.left.clone(),
+                height: self.height,
+                children: self.children
+            }.with_attrs()
+        };
+
+        Rc::new(node).rebalanced()
+    }
+
+    // Returns `None` if the whole (cloned) subtree rooted here should be
+    // removed by the caller.
+    fn delete_player(self: &Rc<Self>, player_id: impl AsRef<str>) -> Option<Rc<Self>> {
+        let ply_id = player_id.as_ref();
+
+        let left = self.left.as_ref().and_then(|l| l.delete_player(ply_id));
+        let right = self.right.as_ref().and_then(|r| r.delete_player(ply_id));
+
+        let mut remaining_players = self.player_id.clone();
+        remaining_players.retain(|p| p != ply_id);
+
+        if remaining_players.is_empty() {
+            return join(left, right);
+        }
+
+        // the player wasn't anywhere in this subtree: share it instead of copying
+        let same = |new: &Option<Rc<Self>>, old: &Option<Rc<Self>>| match (new, old) {
+            (Some(n), Some(o)) => Rc::ptr_eq(n, o),
+            (None, None) => true,
+            _ => false
+        };
+        if remaining_players.len() == self.player_id.len() && same(&left, &self.left) && same(&right, &self.right) {
+            return Some(Rc::clone(self));
+        }
+
+        Some(Rc::new(Self {
+            player_id: remaining_players,
+            score: self.score,
+            right,
+            left,
+            height: self.height,
+            children: self.children
+        }.with_attrs()).rebalanced())
+    }
+
+    // Returns `None` if the whole (cloned) subtree rooted here should be
+    // removed by the caller.
+    fn delete_player_score(&self, player_id: impl AsRef<str>, score: u64) -> Option<Rc<Self>> {
+        if self.score == score {
+            let ply_id = player_id.as_ref();
+            let mut remaining_players = self.player_id.clone();
+            remaining_players.retain(|p| p != ply_id);
+
+            if remaining_players.is_empty() {
+                return join(self.left.clone(), self.right.clone());
+            }
+
+            return Some(Rc::new(Self {
+                player_id: remaining_players,
+                score: self.score,
+                right: self.right.clone(),
+                left: self.left.clone(),
+                height: self.height,
+                children: self.children
+            }.with_attrs()).rebalanced());
+        }
+
+        let node = if score < self.score {
+            let left = self.left.as_ref().and_then(|l| l.delete_player_score(player_id, score));
+            Self {
+                player_id: self.player_id.clone(),
+                score: self.score,
+                right: self.right.clone(),
+                left,
+                height: self.height,
+                children: self.children
+            }
+        } else {
+            let right = self.right.as_ref().and_then(|r| r.delete_player_score(player_id, score));
+            Self {
+                player_id: self.player_id.clone(),
+                score: self.score,
+                right,
+                left: self.left.clone(),
+                height: self.height,
+                children: self.children
+            }
+        }.with_attrs();
+
+        Some(Rc::new(node).rebalanced())
+    }
+
+    pub fn top_n_players(&self, n: usize) -> Vec<(String, u64)> {
+        let mut results = Vec::with_capacity(n);
+
+        if let Some(ref right) = self.right {
+            results.extend(right.top_n_players(n));
+        }
+
+        let mut rem = n - results.len();
+        if rem < 1 {
+            return results;
+        }
+
+        results.extend(self.player_id.iter().enumerate()
+            .take_while(|(i, _)| *i < rem)
+            .map(|(_, v)| (v.clone(), self.score)));
+
+        rem = n - results.len();
+        if let Some(ref left) = self.left {
+            results.extend(left.top_n_players(rem));
+        }
+
+        results
+    }
+
+    pub fn rank_of(&self, player: impl AsRef<str>, score: u64, num_better: usize) -> Option<usize> {
+        let ply_id = player.as_ref();
+        let right_tree_size = self.right.as_ref().map(|r| r.children + 1).unwrap_or(0);
+
+        if self.score == score {
+            if self.player_id.iter().filter(|v| **v == ply_id).count() < 1 {
+                return None;
+            }
+
+            Some(1 + right_tree_size + num_better)
+        } else if score < self.score {
+            self.left.as_ref().and_then(|l| l.rank_of(ply_id, score, 1 + right_tree_size + num_better))
+        } else {
+            self.right.as_ref().and_then(|r| r.rank_of(ply_id, score, num_better))
+        }
+    }
+
+    fn format_string(&self, mut buf: &mut String, level: usize) {
+        let mut players = self.player_id.iter()
+            .fold(String::new(), |mut acc, ply| {
+                if acc.len() > 0 {
+                    acc.push(',');
+                    acc.push(' ');
+                }
+                acc.push_str(ply);
+                acc
+            });
+
+        players.push('(');
+        players.push_str(&self.score.to_string());
+        players.push_str(", ");
+        players.push_str(&self.height.to_string());
+        players.push_str(", ");
+        players.push_str(&self.children.to_string());
+        players.push(')');
+
+        let padding = "\t".repeat(level);
+
+        buf.push_str(&format!("{padding}{players}\n{padding}right:\n"));
+        match &self.right {
+            Some(rn) => rn.format_string(&mut buf, level + 1),
+            None => buf.push_str(&format!("{padding}\t(no right node)\n"))
+        }
+        buf.push_str(&format!("{padding}left:\n"));
+        match &self.left {
+            Some(ln) => ln.format_string(&mut buf, level + 1),
+            None => buf.push_str(&format!("{padding}\t(no left node)\n"))
+        }
+        buf.push('\n');
+    }
+}
+
+impl Display for PersistentNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut buf = String::new();
+        self.format_string(&mut buf, 0);
+        write!(f, "{}", buf)
+    }
+}
+
+// Rebalancing a persistent node means cloning (at most) the node and the
+// one or two descendants along the rotated path -- everything below the
+// rotation point is shared unchanged via `Rc::clone`.
+trait Rebalance {
+    fn rebalanced(self) -> Rc<PersistentNode>;
+}
+
+impl Rebalance for Rc<PersistentNode> {
+    fn rebalanced(self) -> Rc<PersistentNode> {
+        if !self.unbalanced() {
+            return self;
+        }
+
+        if self.height_right() - self.height_left() > 1 {
+            let r = self.right.as_ref().unwrap();
+            // >= (not >): a balanced child (equal heights, which delete/join can
+            // produce) still needs the single rotation, not the double one
+            let rr_heavy = r.height_right() >= r.height_left();
+
+            if rr_heavy {
+                rotate_left(self)
+            } else {
+                let new_right = rotate_right(Rc::clone(r));
+                let mut a = (*self).clone_shallow();
+                a.right = Some(new_right);
+                rotate_left(Rc::new(a.with_attrs()))
+            }
+        } else {
+            let l = self.left.as_ref().unwrap();
+            let ll_heavy = l.height_left() >= l.height_right();
+
+            if ll_heavy {
+                rotate_right(self)
+            } else {
+                let new_left = rotate_left(Rc::clone(l));
+                let mut a = (*self).clone_shallow();
+                a.left = Some(new_left);
+                rotate_right(Rc::new(a.with_attrs()))
+            }
+        }
+    }
+}
+
+impl PersistentNode {
+    // A shallow clone: copies the node's own fields, sharing (not deep-copying)
+    // its children via `Rc::clone`.
+    fn clone_shallow(&self) -> Self {
+        Self {
+            player_id: self.player_id.clone(),
+            score: self.score,
+            right: self.right.clone(),
+            left: self.left.clone(),
+            height: self.height,
+            children: self.children
+        }
+    }
+}
+
+/*
+    a
+     \
+      b
+       \
+        c
+
+ To fix this, b becomes the new root with a as the left child and c as the right child
+ */
+fn rotate_left(a: Rc<PersistentNode>) -> Rc<PersistentNode> {
+    let mut b = (*a.right.as_ref().unwrap()).clone_shallow();
+    let mut a = (*a).clone_shallow();
+
+    a.right = b.left.take();
+    a = a.with_attrs();
+
+    b.left = Some(Rc::new(a));
+    Rc::new(b.with_attrs())
+}
+
+/*
+          c
+         /                  b
+       b           ->     /   \
+      /                  a     c
+    a
+
+B becomes the new root, c takes ownership of b's right child as its left child,
+B takes ownership of c as its right child
+*/
+fn rotate_right(c: Rc<PersistentNode>) -> Rc<PersistentNode> {
+    let mut b = (*c.left.as_ref().unwrap()).clone_shallow();
+    let mut c = (*c).clone_shallow();
+
+    c.left = b.right.take();
+    c = c.with_attrs();
+
+    b.right = Some(Rc::new(c));
+    Rc::new(b.with_attrs())
+}
+
+// Join two subtrees whose score ranges don't overlap (as produced by deleting
+// a node out from between them) into one AVL-balanced subtree.
+fn join(left: Option<Rc<PersistentNode>>, right: Option<Rc<PersistentNode>>) -> Option<Rc<PersistentNode>> {
+    match (left, right) {
+        (None, right) => right,
+        (left, None) => left,
+        (Some(l), Some(r)) => {
+            let (min_node, remainder) = pop_min(r);
+
+            let mut mid = min_node.clone_shallow();
+            mid.left = Some(l);
+            mid.right = remainder;
+
+            Some(Rc::new(mid.with_attrs()).rebalanced())
+        }
+    }
+}
+
+fn pop_min(node: Rc<PersistentNode>) -> (Rc<PersistentNode>, Option<Rc<PersistentNode>>) {
+    match &node.left {
+        None => (Rc::clone(&node), node.right.clone()),
+        Some(left) => {
+            let (min_node, new_left) = pop_min(Rc::clone(left));
+
+            let mut remainder = node.clone_shallow();
+            remainder.left = new_left;
+
+            (min_node, Some(Rc::new(remainder.with_attrs()).rebalanced()))
+        }
+    }
+}